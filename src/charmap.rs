@@ -0,0 +1,109 @@
+//! Translation from Unicode `char`s to HD44780 character-ROM byte codes.
+
+/// Maps a Unicode character to its byte code in a particular HD44780 ROM.
+///
+/// Returns `None` when the ROM has no glyph for `c`, letting callers substitute
+/// a replacement byte of their choosing.
+pub trait CharMap {
+    /// Returns the ROM code for `c`, or `None` if the ROM can't render it.
+    fn map(&self, c: char) -> Option<u8>;
+}
+
+/// A00 "Japanese" character ROM.
+///
+/// Printable ASCII maps to itself except for the two codes the ROM reassigns:
+/// 0x5C is `¥` and 0x7E is `→`. A compact table covers the common symbols and
+/// Greek letters in the upper half of the ROM.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct RomA00;
+
+/// Symbols and Greek letters outside the ASCII range of the A00 ROM.
+const A00_TABLE: &[(char, u8)] = &[
+    ('¥', 0x5C),
+    ('→', 0x7E),
+    ('←', 0x7F),
+    ('α', 0xE0),
+    ('ä', 0xE1),
+    ('β', 0xE2),
+    ('ε', 0xE3),
+    ('µ', 0xE4),
+    ('σ', 0xE5),
+    ('ρ', 0xE6),
+    ('√', 0xE8),
+    ('¢', 0xEC),
+    ('ñ', 0xEE),
+    ('ö', 0xEF),
+    ('θ', 0xF2),
+    ('∞', 0xF3),
+    ('Ω', 0xF4),
+    ('ü', 0xF5),
+    ('Σ', 0xF6),
+    ('π', 0xF7),
+    ('÷', 0xFD),
+    ('°', 0xDF),
+];
+
+impl CharMap for RomA00 {
+    fn map(&self, c: char) -> Option<u8> {
+        // Printable ASCII passes through, minus the two reassigned codes.
+        if (' '..='}').contains(&c) {
+            let b = c as u8;
+            if b != 0x5C {
+                return Some(b);
+            }
+        }
+        A00_TABLE
+            .iter()
+            .find(|(glyph, _)| *glyph == c)
+            .map(|(_, code)| *code)
+    }
+}
+
+/// A02 "European" character ROM.
+///
+/// The A02 ROM keeps the full ASCII range (including `~` at 0x7E) and follows
+/// ISO-8859-1 across its upper half, so ASCII and Latin-1 codepoints pass
+/// straight through. The directional arrows live in the 0x10-0x1F symbol block,
+/// not over ASCII, so they need an explicit mapping.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct RomA02;
+
+impl CharMap for RomA02 {
+    fn map(&self, c: char) -> Option<u8> {
+        match c {
+            '→' => Some(0x10),
+            '←' => Some(0x11),
+            ' '..='\u{7F}' => Some(c as u8),
+            '\u{A0}'..='\u{FF}' => Some(c as u8),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rom_a00_ascii_and_symbols() {
+        let rom = RomA00;
+        assert_eq!(rom.map('A'), Some(0x41));
+        assert_eq!(rom.map('¥'), Some(0x5C));
+        assert_eq!(rom.map('→'), Some(0x7E));
+        assert_eq!(rom.map('°'), Some(0xDF));
+        // Backslash shares its code with ¥ and is unavailable.
+        assert_eq!(rom.map('\\'), None);
+        assert_eq!(rom.map('☃'), None);
+    }
+
+    #[test]
+    fn rom_a02_is_latin1() {
+        let rom = RomA02;
+        assert_eq!(rom.map('A'), Some(0x41));
+        assert_eq!(rom.map('°'), Some(0xB0));
+        assert_eq!(rom.map('ü'), Some(0xFC));
+        assert_eq!(rom.map('~'), Some(0x7E));
+        assert_eq!(rom.map('→'), Some(0x10));
+        assert_eq!(rom.map('☃'), None);
+    }
+}
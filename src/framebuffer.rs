@@ -0,0 +1,179 @@
+use crate::hd44780;
+use crate::screen::Screen;
+
+/// Returns the DDRAM base address of `row` on a `WIDTH`-column panel.
+///
+/// HD44780 rows are not contiguous: lines 0 and 2 start at 0x00 and 0x00+WIDTH,
+/// lines 1 and 3 at 0x40 and 0x40+WIDTH.
+const fn line_offset(row: usize, width: usize) -> u8 {
+    let base = if row & 1 == 0 { 0x00 } else { 0x40 };
+    (base + (row / 2) * width) as u8
+}
+
+/// In-memory shadow of the screen contents that flushes only changed cells.
+///
+/// The buffer mirrors exactly what each DDRAM cell currently holds, so a batch
+/// of edits can be transmitted as a minimal diff: on `flush()` the changed
+/// columns of each row are coalesced into contiguous runs, and each run is sent
+/// as one addressed write, never re-transmitting unchanged characters or
+/// re-issuing a cursor command per byte.
+#[derive(Debug)]
+pub struct FrameBuffer<const WIDTH: usize, const HEIGHT: usize> {
+    /// Row-major shadow, stored as `HEIGHT` rows of `WIDTH` cells to avoid a
+    /// `WIDTH * HEIGHT` const expression (unstable on an array length).
+    shadow: [[u8; WIDTH]; HEIGHT],
+    /// Per-cell dirty flags, so flush can emit only contiguous changed runs.
+    dirty: [[bool; WIDTH]; HEIGHT],
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> FrameBuffer<WIDTH, HEIGHT> {
+    /// Creates a frame buffer initialised to spaces, with every cell clean.
+    pub fn new() -> Self {
+        Self {
+            shadow: [[b' '; WIDTH]; HEIGHT],
+            dirty: [[false; WIDTH]; HEIGHT],
+        }
+    }
+
+    /// Stores `byte` at `(col, row)`, marking the cell dirty if it changed.
+    ///
+    /// Out-of-bounds coordinates are ignored.
+    pub fn set(&mut self, col: usize, row: usize, byte: u8) {
+        if col >= WIDTH || row >= HEIGHT {
+            return;
+        }
+        let cell = &mut self.shadow[row][col];
+        if *cell == byte {
+            return;
+        }
+        *cell = byte;
+        self.dirty[row][col] = true;
+    }
+
+    /// Writes the bytes of `s` starting at `(col, row)`, clipping at the row end.
+    pub fn write_at(&mut self, col: usize, row: usize, s: &[u8]) {
+        for (i, byte) in s.iter().enumerate() {
+            self.set(col + i, row, *byte);
+        }
+    }
+
+    /// Transmits every contiguous run of changed cells and marks them clean.
+    ///
+    /// For each row, adjacent dirty columns are coalesced into a run; each run
+    /// emits one `set_ddram_address` command followed by a single
+    /// `send_data_bytes` call spanning just that run.
+    pub fn flush<E, S>(&mut self, screen: &mut S) -> Result<(), E>
+    where
+        S: Screen<WIDTH, HEIGHT, E>,
+    {
+        for row in 0..HEIGHT {
+            let base = line_offset(row, WIDTH);
+            let mut col = 0;
+            while col < WIDTH {
+                if !self.dirty[row][col] {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < WIDTH && self.dirty[row][col] {
+                    self.dirty[row][col] = false;
+                    col += 1;
+                }
+                screen.send_command(hd44780::set_ddram_address(base + start as u8))?;
+                screen.send_data_bytes(&self.shadow[row][start..col])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Default for FrameBuffer<WIDTH, HEIGHT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    enum ScreenError {}
+
+    #[derive(Debug, Default)]
+    struct TestScreen {
+        commands: Vec<u8>,
+        data: Vec<u8>,
+    }
+
+    impl Screen<16, 2, ScreenError> for TestScreen {
+        fn send_command(&mut self, command: u8) -> Result<(), ScreenError> {
+            self.commands.push(command);
+            Ok(())
+        }
+
+        fn send_data(&mut self, data: u8) -> Result<(), ScreenError> {
+            self.data.push(data);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flush_sends_only_dirty_span() {
+        let mut fb = FrameBuffer::<16, 2>::new();
+        fb.write_at(3, 0, b"hi");
+
+        let mut screen = TestScreen::default();
+        fb.flush(&mut screen).unwrap();
+
+        assert_eq!(screen.commands, vec![hd44780::set_ddram_address(3)]);
+        assert_eq!(screen.data.as_slice(), b"hi");
+    }
+
+    #[test]
+    fn flush_uses_second_line_offset() {
+        let mut fb = FrameBuffer::<16, 2>::new();
+        fb.write_at(0, 1, b"x");
+
+        let mut screen = TestScreen::default();
+        fb.flush(&mut screen).unwrap();
+
+        assert_eq!(screen.commands, vec![hd44780::set_ddram_address(0x40)]);
+        assert_eq!(screen.data.as_slice(), b"x");
+    }
+
+    #[test]
+    fn separate_runs_skip_unchanged_cells() {
+        let mut fb = FrameBuffer::<16, 2>::new();
+        fb.set(0, 0, b'A');
+        fb.set(15, 0, b'Z');
+
+        let mut screen = TestScreen::default();
+        fb.flush(&mut screen).unwrap();
+
+        // Two separate runs, not one 16-cell span.
+        assert_eq!(
+            screen.commands,
+            vec![
+                hd44780::set_ddram_address(0),
+                hd44780::set_ddram_address(15)
+            ]
+        );
+        assert_eq!(screen.data.as_slice(), b"AZ");
+    }
+
+    #[test]
+    fn unchanged_cells_are_not_resent() {
+        let mut fb = FrameBuffer::<16, 2>::new();
+        fb.write_at(0, 0, b"abc");
+        fb.flush(&mut TestScreen::default()).unwrap();
+
+        // Rewriting the same bytes leaves the row clean.
+        fb.write_at(0, 0, b"abc");
+        let mut screen = TestScreen::default();
+        fb.flush(&mut screen).unwrap();
+
+        assert_eq!(screen.commands, vec![]);
+        assert_eq!(screen.data, vec![]);
+    }
+}
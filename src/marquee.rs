@@ -0,0 +1,177 @@
+use crate::hd44780;
+use crate::screen::Screen;
+
+/// Fill levels of a [`Marquee`], analogous to a TCP send/receive buffer query.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Limits {
+    /// Total number of bytes currently held in the ring buffer.
+    pub total: usize,
+    /// Number of bytes made visible on the panel at once.
+    pub window: usize,
+    /// Fixed target capacity of the ring buffer.
+    pub capacity: usize,
+}
+
+/// Auto-scrolling text region backed by a fixed-capacity ring buffer.
+///
+/// Holds strings longer than the physical `WIDTH` and renders a `WIDTH`-byte
+/// window over them, so log lines or notifications that don't fit the panel can
+/// scroll without reallocating every frame. New text is appended at the tail
+/// while the head is consumed, wrapping around the `CAP`-byte store.
+#[derive(Debug)]
+pub struct Marquee<const WIDTH: usize, const CAP: usize> {
+    buf: [u8; CAP],
+    /// Index of the oldest stored byte.
+    head: usize,
+    /// Number of bytes currently stored.
+    len: usize,
+    /// Window start, as a logical offset into the stored content.
+    offset: usize,
+}
+
+impl<const WIDTH: usize, const CAP: usize> Marquee<WIDTH, CAP> {
+    /// Creates an empty marquee.
+    pub fn new() -> Self {
+        Self {
+            buf: [b' '; CAP],
+            head: 0,
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    /// Reports the current fill versus the visible window and target capacity.
+    pub fn limits(&self) -> Limits {
+        Limits {
+            total: self.len,
+            window: WIDTH,
+            capacity: CAP,
+        }
+    }
+
+    /// Appends `s` to the tail, dropping the oldest bytes once capacity is hit.
+    pub fn push_str(&mut self, s: &str) {
+        for &byte in s.as_bytes() {
+            if self.len == CAP {
+                // Buffer full: consume one byte from the head to make room.
+                self.head = (self.head + 1) % CAP;
+                self.len -= 1;
+                self.offset = self.offset.saturating_sub(1);
+            }
+            let tail = (self.head + self.len) % CAP;
+            self.buf[tail] = byte;
+            self.len += 1;
+        }
+    }
+
+    /// Scrolls the visible window one column towards the end of the text.
+    pub fn scroll_left(&mut self) {
+        if self.len != 0 {
+            self.offset = (self.offset + 1) % self.len;
+        }
+    }
+
+    /// Scrolls the visible window one column towards the start of the text.
+    pub fn scroll_right(&mut self) {
+        if self.len != 0 {
+            self.offset = (self.offset + self.len - 1) % self.len;
+        }
+    }
+
+    /// Advances the window one column and renders it to the screen.
+    pub fn tick<E, S>(&mut self, screen: &mut S) -> Result<(), E>
+    where
+        S: Screen<WIDTH, 1, E>,
+    {
+        self.scroll_left();
+        self.render(screen)
+    }
+
+    /// Positions the cursor at the start of the region and writes `WIDTH` bytes.
+    pub fn render<E, S>(&mut self, screen: &mut S) -> Result<(), E>
+    where
+        S: Screen<WIDTH, 1, E>,
+    {
+        let mut window = [b' '; WIDTH];
+        if self.len != 0 {
+            for (i, slot) in window.iter_mut().enumerate() {
+                let logical = (self.offset + i) % self.len;
+                *slot = self.buf[(self.head + logical) % CAP];
+            }
+        }
+        screen.send_command(hd44780::set_ddram_address(0))?;
+        screen.send_data_bytes(&window)
+    }
+}
+
+impl<const WIDTH: usize, const CAP: usize> Default for Marquee<WIDTH, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    enum ScreenError {}
+
+    #[derive(Debug, Default)]
+    struct TestScreen {
+        commands: Vec<u8>,
+        data: Vec<u8>,
+    }
+
+    impl Screen<4, 1, ScreenError> for TestScreen {
+        fn send_command(&mut self, command: u8) -> Result<(), ScreenError> {
+            self.commands.push(command);
+            Ok(())
+        }
+
+        fn send_data(&mut self, data: u8) -> Result<(), ScreenError> {
+            self.data.push(data);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn renders_visible_window() {
+        let mut m = Marquee::<4, 16>::new();
+        m.push_str("hello");
+        assert_eq!(
+            m.limits(),
+            Limits {
+                total: 5,
+                window: 4,
+                capacity: 16
+            }
+        );
+
+        let mut screen = TestScreen::default();
+        m.render(&mut screen).unwrap();
+        assert_eq!(screen.commands, vec![hd44780::set_ddram_address(0)]);
+        assert_eq!(screen.data.as_slice(), b"hell");
+    }
+
+    #[test]
+    fn tick_advances_window() {
+        let mut m = Marquee::<4, 16>::new();
+        m.push_str("hello");
+
+        let mut screen = TestScreen::default();
+        m.tick(&mut screen).unwrap();
+        assert_eq!(screen.data.as_slice(), b"ello");
+    }
+
+    #[test]
+    fn push_str_drops_oldest_when_full() {
+        let mut m = Marquee::<4, 4>::new();
+        m.push_str("abcdef");
+        assert_eq!(m.limits().total, 4);
+
+        let mut screen = TestScreen::default();
+        m.render(&mut screen).unwrap();
+        assert_eq!(screen.data.as_slice(), b"cdef");
+    }
+}
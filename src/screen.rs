@@ -1,3 +1,6 @@
+use core::ops::Range;
+
+use crate::charmap::CharMap;
 use crate::hd44780;
 
 /// Generic code for LCD screen of a given size.
@@ -47,6 +50,116 @@ pub trait Screen<const WIDTH: usize, const HEIGHT: usize, Error> {
 
         self.send_data_bytes(&string_buf[..len])
     }
+
+    /// Prints `s`, translating each character through `map`.
+    ///
+    /// Characters the ROM can't render are emitted as `replacement`. Unlike
+    /// `write`, this correctly renders ROM-specific glyphs such as `°`, `¥`,
+    /// and the arrows instead of casting codepoints straight to bytes.
+    fn write_mapped<M: CharMap>(
+        &mut self,
+        s: &str,
+        map: &M,
+        replacement: u8,
+    ) -> Result<(), Error> {
+        let mut string_buf = [0; WIDTH];
+
+        let len = s
+            .chars()
+            .take(WIDTH)
+            .map(|c| map.map(c).unwrap_or(replacement))
+            .fold(0, |i, b| {
+                string_buf[i] = b;
+                i + 1
+            });
+
+        self.send_data_bytes(&string_buf[..len])
+    }
+
+    /// Programs one of the 8 user-definable characters (CGRAM slots 0-7).
+    ///
+    /// `bitmap` holds 8 rows of 5-bit pixels, top to bottom. The glyph can then
+    /// be printed via `write` using the matching character code 0-7.
+    fn define_glyph(&mut self, slot: u8, bitmap: &[u8; 8]) -> Result<(), Error> {
+        self.send_command(hd44780::set_cgram_address(slot * 8))?;
+        self.send_data_bytes(bitmap)
+    }
+
+    /// Decompresses and uploads a whole bank of glyphs in one call.
+    ///
+    /// `packed` is a PackBits-style stream shared by all glyphs in `slots`:
+    /// each control byte `n` either repeats the following byte `(!n)+2` times
+    /// (high bit set) or copies the next `n+1` bytes verbatim. Exactly 8 bytes
+    /// are decoded per slot into a stack buffer before upload.
+    fn define_glyphs_rle(&mut self, slots: Range<u8>, packed: &[u8]) -> Result<(), Error> {
+        let mut pos = 0;
+        for slot in slots {
+            let mut bitmap = [0u8; 8];
+            pos += unpack_glyph(&packed[pos..], &mut bitmap);
+            self.define_glyph(slot, &bitmap)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor to `(col, row)` using the HD44780 DDRAM layout.
+    ///
+    /// Rows are non-contiguous: lines 0 and 2 start at 0x00 and 0x00+WIDTH,
+    /// lines 1 and 3 at 0x40 and 0x40+WIDTH, covering 1/2/4-line panels.
+    fn set_cursor(&mut self, col: usize, row: usize) -> Result<(), Error> {
+        let base: u8 = if row & 1 == 0 { 0x00 } else { 0x40 };
+        let addr = base + ((row / 2) * WIDTH + col) as u8;
+        self.send_command(hd44780::set_ddram_address(addr))
+    }
+
+    /// Positions the cursor at `(col, row)` and prints `s`, clipping at the row
+    /// end. Control characters are not supported.
+    fn write_at(&mut self, col: usize, row: usize, s: &str) -> Result<(), Error> {
+        self.set_cursor(col, row)?;
+
+        let mut string_buf = [0; WIDTH];
+        let avail = WIDTH.saturating_sub(col);
+        let len = s
+            .chars()
+            .take(avail)
+            .map(|c| if (c as u32) < 256 { c } else { '?' })
+            .fold(0, |i, c| {
+                string_buf[i] = c as u8;
+                i + 1
+            });
+
+        self.send_data_bytes(&string_buf[..len])
+    }
+}
+
+/// Decodes exactly 8 PackBits-compressed bytes from `packed` into `out`,
+/// returning the number of input bytes consumed.
+///
+/// Run lengths are clamped to the remaining space so a malformed bank whose run
+/// crosses the 8-byte boundary stops at the glyph edge instead of panicking.
+fn unpack_glyph(packed: &[u8], out: &mut [u8; 8]) -> usize {
+    let mut src = 0;
+    let mut dst = 0;
+    while dst < out.len() {
+        let ctrl = packed[src];
+        src += 1;
+        if ctrl & 0x80 != 0 {
+            let len = ((!ctrl as usize) + 2).min(out.len() - dst);
+            let value = packed[src];
+            src += 1;
+            for _ in 0..len {
+                out[dst] = value;
+                dst += 1;
+            }
+        } else {
+            let len = (ctrl as usize + 1).min(out.len() - dst);
+            for _ in 0..len {
+                out[dst] = packed[src];
+                dst += 1;
+                src += 1;
+            }
+        }
+    }
+    src
 }
 
 #[cfg(test)]
@@ -100,4 +213,81 @@ mod tests {
         assert_eq!(screen.commands, vec![]);
         assert_eq!(screen.data.as_slice(), b"this is very lon");
     }
+
+    #[test]
+    fn define_glyph() {
+        let mut screen = TestScreen::new();
+        let bitmap = [0x04, 0x0e, 0x1f, 0x04, 0x04, 0x04, 0x00, 0x00];
+        screen.define_glyph(2, &bitmap).unwrap();
+        assert_eq!(screen.commands, vec![hd44780::set_cgram_address(16)]);
+        assert_eq!(screen.data.as_slice(), &bitmap);
+    }
+
+    #[test]
+    fn define_glyphs_rle() {
+        let mut screen = TestScreen::new();
+        // Two glyphs: the first all zeros, the second a literal run.
+        let glyph0 = [0u8; 8];
+        let glyph1 = [0x01, 0x02, 0x04, 0x08, 0x10, 0x01, 0x02, 0x04];
+        // 0xF9 repeats the next byte 8 times; 0x07 copies 8 literal bytes.
+        let packed = [
+            0xF9, 0x00, 0x07, 0x01, 0x02, 0x04, 0x08, 0x10, 0x01, 0x02, 0x04,
+        ];
+
+        screen.define_glyphs_rle(0..2, &packed).unwrap();
+
+        assert_eq!(
+            screen.commands,
+            vec![hd44780::set_cgram_address(0), hd44780::set_cgram_address(8)]
+        );
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&glyph0);
+        expected.extend_from_slice(&glyph1);
+        assert_eq!(screen.data, expected);
+    }
+
+    #[test]
+    fn write_mapped() {
+        use crate::charmap::RomA00;
+
+        let mut screen = TestScreen::new();
+        screen.write_mapped("5\u{B0}C", &RomA00, b'?').unwrap();
+        assert_eq!(screen.commands, vec![]);
+        // '5' and 'C' pass through; '°' maps to the A00 code 0xDF.
+        assert_eq!(screen.data, vec![b'5', 0xDF, b'C']);
+    }
+
+    #[derive(Debug, Default)]
+    struct TestScreen2 {
+        commands: Vec<u8>,
+        data: Vec<u8>,
+    }
+
+    impl Screen<16, 2, ScreenError> for TestScreen2 {
+        fn send_command(&mut self, command: u8) -> Result<(), ScreenError> {
+            self.commands.push(command);
+            Ok(())
+        }
+
+        fn send_data(&mut self, data: u8) -> Result<(), ScreenError> {
+            self.data.push(data);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn set_cursor_second_line() {
+        let mut screen = TestScreen2::default();
+        screen.set_cursor(3, 1).unwrap();
+        assert_eq!(screen.commands, vec![hd44780::set_ddram_address(0x43)]);
+        assert_eq!(screen.data, vec![]);
+    }
+
+    #[test]
+    fn write_at_positions_and_clips() {
+        let mut screen = TestScreen2::default();
+        screen.write_at(14, 1, "abcd").unwrap();
+        assert_eq!(screen.commands, vec![hd44780::set_ddram_address(0x4e)]);
+        assert_eq!(screen.data.as_slice(), b"ab");
+    }
 }